@@ -0,0 +1,142 @@
+//! `meraki` - a companion CLI that talks to the running GUI's backend over
+//! its HTTP API, so workflows can be scripted without the webview.
+use clap::{Parser, Subcommand};
+use std::time::{Duration, Instant};
+
+#[path = "../http_client.rs"]
+mod http_client;
+#[path = "../port_file.rs"]
+mod port_file;
+
+#[derive(Parser)]
+#[command(name = "meraki", about = "Command-line companion for Meraki Workflow")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Check whether the backend is healthy
+    Health,
+    /// Run a named workflow against the backend
+    Run { workflow: String },
+    /// Raise the GUI window, launching the app first if it isn't running
+    Show,
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Health => health().await,
+        Command::Run { workflow } => run_workflow(&workflow).await,
+        Command::Show => show(),
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn health() -> Result<(), String> {
+    let base_url = backend_base_url().await?;
+    let response = http_client::build_from_env()
+        .get(format!("{}/api/v1/health", base_url))
+        .send()
+        .await
+        .map_err(|e| format!("Backend is not responding: {}", e))?;
+
+    if response.status().is_success() {
+        println!("Backend is healthy");
+        Ok(())
+    } else {
+        Err(format!("Backend returned status: {}", response.status()))
+    }
+}
+
+async fn run_workflow(workflow: &str) -> Result<(), String> {
+    let base_url = backend_base_url().await?;
+    let response = http_client::build_from_env()
+        .post(format!("{}/api/v1/workflows/{}/run", base_url, workflow))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to run workflow '{}': {}", workflow, e))?;
+
+    if response.status().is_success() {
+        println!("Workflow '{}' started", workflow);
+        Ok(())
+    } else {
+        Err(format!("Backend returned status: {}", response.status()))
+    }
+}
+
+/// Raise the GUI window. Relies on the app's single-instance guard: if it's
+/// already running, this launch is forwarded as argv and focuses the
+/// existing window instead of starting a second instance.
+fn show() -> Result<(), String> {
+    launch_gui()
+}
+
+/// Resolve the backend's base URL, launching the GUI and waiting for it to
+/// come up if it isn't running yet
+async fn backend_base_url() -> Result<String, String> {
+    if let Ok(port) = port_file::read() {
+        let base_url = format!("http://127.0.0.1:{}", port);
+        if probe_health(&base_url).await {
+            return Ok(base_url);
+        }
+    }
+
+    println!("Meraki Workflow isn't running, launching it...");
+    launch_gui()?;
+    wait_for_backend().await
+}
+
+fn launch_gui() -> Result<(), String> {
+    let current_exe = std::env::current_exe()
+        .map_err(|e| format!("Failed to locate the meraki executable: {}", e))?;
+    // The GUI binary is built from the same package as this CLI, so its name
+    // tracks `CARGO_PKG_NAME` rather than a guessed literal that would drift
+    // if the package were ever renamed.
+    let gui_name = if cfg!(windows) {
+        format!("{}.exe", env!("CARGO_PKG_NAME"))
+    } else {
+        env!("CARGO_PKG_NAME").to_string()
+    };
+    let gui_exe = current_exe.with_file_name(gui_name);
+
+    std::process::Command::new(gui_exe)
+        .spawn()
+        .map_err(|e| format!("Failed to launch Meraki Workflow: {}", e))?;
+    Ok(())
+}
+
+async fn wait_for_backend() -> Result<String, String> {
+    let timeout = Duration::from_secs(15);
+    let start = Instant::now();
+
+    while start.elapsed() < timeout {
+        if let Ok(port) = port_file::read() {
+            let base_url = format!("http://127.0.0.1:{}", port);
+            if probe_health(&base_url).await {
+                return Ok(base_url);
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+
+    Err("Timed out waiting for Meraki Workflow to start".to_string())
+}
+
+async fn probe_health(base_url: &str) -> bool {
+    http_client::build_from_env()
+        .get(format!("{}/api/v1/health", base_url))
+        .timeout(Duration::from_secs(2))
+        .send()
+        .await
+        .map(|r| r.status().is_success())
+        .unwrap_or(false)
+}
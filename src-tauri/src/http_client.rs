@@ -0,0 +1,93 @@
+use reqwest::{Client, ClientBuilder, Proxy};
+use tauri::{AppHandle, Manager, Runtime};
+
+/// Name of the persisted network config file within the app's config directory
+const CONFIG_FILE_NAME: &str = "network.json";
+
+/// Build a `reqwest::Client` that honors a persisted proxy override or,
+/// failing that, the `HTTP_PROXY`/`HTTPS_PROXY`/`ALL_PROXY` environment
+/// variables (including `socks5://` URLs). Used for both the backend health
+/// probe and anywhere else the GUI talks to the network, so both paths work
+/// the same way behind a corporate proxy.
+pub fn build<R: Runtime>(app: &AppHandle<R>) -> Client {
+    finish(apply_proxy(Client::builder(), proxy_override(app)))
+}
+
+/// Same as `build`, but for call sites with no `AppHandle` (the `meraki`
+/// CLI), so only the environment variables are consulted
+pub fn build_from_env() -> Client {
+    finish(apply_proxy(Client::builder(), None))
+}
+
+fn finish(builder: ClientBuilder) -> Client {
+    builder.build().unwrap_or_else(|e| {
+        eprintln!(
+            "Failed to build proxy-aware HTTP client, falling back to a direct one: {}",
+            e
+        );
+        Client::new()
+    })
+}
+
+/// Apply `override_url` if set, else `ALL_PROXY`, else `HTTP_PROXY`/`HTTPS_PROXY`
+/// individually
+fn apply_proxy(builder: ClientBuilder, override_url: Option<String>) -> ClientBuilder {
+    if let Some(url) = override_url.or_else(|| env_var("ALL_PROXY")) {
+        return with_proxy(builder, Proxy::all(&url), &url);
+    }
+
+    let mut builder = builder;
+    if let Some(url) = env_var("HTTP_PROXY") {
+        builder = with_proxy(builder, Proxy::http(&url), &url);
+    }
+    if let Some(url) = env_var("HTTPS_PROXY") {
+        builder = with_proxy(builder, Proxy::https(&url), &url);
+    }
+    builder
+}
+
+fn with_proxy(builder: ClientBuilder, proxy: reqwest::Result<Proxy>, url: &str) -> ClientBuilder {
+    match proxy {
+        Ok(proxy) => builder.proxy(proxy),
+        Err(e) => {
+            eprintln!("Ignoring invalid proxy URL '{}': {}", url, e);
+            builder
+        }
+    }
+}
+
+/// Explicit proxy URL read from the persisted network config, taking
+/// precedence over environment variables when set
+fn proxy_override<R: Runtime>(app: &AppHandle<R>) -> Option<String> {
+    let dir = app.path().app_config_dir().ok()?;
+    let contents = std::fs::read_to_string(dir.join(CONFIG_FILE_NAME)).ok()?;
+    serde_json::from_str::<serde_json::Value>(&contents)
+        .ok()?
+        .get("proxy")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+/// Proxy URL for `tauri_plugin_updater`, which configures its own client at
+/// plugin-registration time (before an `AppHandle` exists), so only the
+/// environment variables are available here, not the persisted override
+pub fn updater_proxy_url() -> Option<url::Url> {
+    let raw = env_var("ALL_PROXY")
+        .or_else(|| env_var("HTTPS_PROXY"))
+        .or_else(|| env_var("HTTP_PROXY"))?;
+
+    match raw.parse() {
+        Ok(url) => Some(url),
+        Err(e) => {
+            eprintln!("Ignoring invalid updater proxy URL '{}': {}", raw, e);
+            None
+        }
+    }
+}
+
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .or_else(|| std::env::var(name.to_lowercase()).ok())
+        .filter(|v| !v.is_empty())
+}
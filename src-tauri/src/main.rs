@@ -1,9 +1,12 @@
 // Prevents additional console window on Windows in release mode
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod commands;
+mod http_client;
+mod port_file;
+mod shortcuts;
 mod sidecar;
 mod tray;
-mod commands;
 mod updater;
 
 use sidecar::SidecarManager;
@@ -11,23 +14,25 @@ use std::sync::Arc;
 use tauri::{Manager, State};
 
 /// Tauri state for the sidecar manager
-struct AppState {
-    sidecar: Arc<SidecarManager>,
+pub(crate) struct AppState {
+    pub(crate) sidecar: Arc<SidecarManager>,
 }
 
 /// Tauri command to check if the backend is healthy
 #[tauri::command]
-async fn check_backend_health() -> Result<String, String> {
-    let client = reqwest::Client::new();
+async fn check_backend_health(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let base_url = state.sidecar.base_url()?;
+    let client = http_client::build(&app);
 
     match client
-        .get("http://127.0.0.1:3141/api/v1/health")
+        .get(format!("{}/api/v1/health", base_url))
         .send()
         .await
     {
-        Ok(response) if response.status().is_success() => {
-            Ok("Backend is healthy".to_string())
-        }
+        Ok(response) if response.status().is_success() => Ok("Backend is healthy".to_string()),
         Ok(response) => Err(format!("Backend returned status: {}", response.status())),
         Err(e) => Err(format!("Backend is not responding: {}", e)),
     }
@@ -39,45 +44,24 @@ fn is_backend_running(state: State<AppState>) -> bool {
     state.sidecar.is_running()
 }
 
-/// Setup global keyboard shortcut (Cmd+Shift+M on macOS, Ctrl+Shift+M on others)
-fn setup_global_shortcut(app: &tauri::AppHandle) -> Result<(), String> {
-    use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
-
-    // Determine platform-specific shortcut
-    let shortcut_str = if cfg!(target_os = "macos") {
-        "Cmd+Shift+M"
-    } else {
-        "Ctrl+Shift+M"
-    };
-
-    let shortcut: Shortcut = shortcut_str
-        .parse()
-        .map_err(|e| format!("Failed to parse shortcut: {:?}", e))?;
-
-    let app_handle = app.clone();
-    app.global_shortcut()
-        .on_shortcut(shortcut, move |_app, _shortcut, _event| {
-            if let Some(window) = app_handle.get_webview_window("main") {
-                if window.is_visible().unwrap_or(false) {
-                    // Window is visible, focus it
-                    if let Err(e) = window.set_focus() {
-                        eprintln!("Failed to focus window: {}", e);
-                    }
-                } else {
-                    // Window is hidden, show it
-                    if let Err(e) = window.show() {
-                        eprintln!("Failed to show window: {}", e);
-                    }
-                    if let Err(e) = window.set_focus() {
-                        eprintln!("Failed to focus window: {}", e);
-                    }
-                }
-            }
-        })
-        .map_err(|e| format!("Failed to register shortcut: {:?}", e))?;
+/// Tauri command exposing the sidecar's current base URL so the frontend
+/// doesn't need to hardcode a port
+#[tauri::command]
+fn get_backend_url(state: State<AppState>) -> Result<String, String> {
+    state.sidecar.base_url()
+}
 
-    println!("Global shortcut registered: {}", shortcut_str);
-    Ok(())
+/// Tauri command returning the buffered sidecar stdout/stderr lines
+#[tauri::command]
+fn get_sidecar_logs(state: State<AppState>) -> Vec<String> {
+    state.sidecar.logs()
+}
+
+/// Tauri command to manually stop and restart the sidecar, e.g. from a
+/// "Reconnect" button on the frontend's connection banner
+#[tauri::command]
+async fn restart_backend(app: tauri::AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    state.sidecar.restart(&app).await
 }
 
 #[tokio::main]
@@ -87,30 +71,59 @@ async fn main() {
     let sidecar_clone = Arc::clone(&sidecar);
 
     tauri::Builder::default()
+        .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+            // A second launch was attempted; just surface the already-running instance
+            // instead of starting a second sidecar on top of the first.
+            println!("Another instance was launched, focusing existing window instead");
+            tray::toggle_main_window(app);
+        }))
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_autostart::init(
             tauri_plugin_autostart::MacosLauncher::LaunchAgent,
             None,
         ))
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
-        .plugin(tauri_plugin_updater::Builder::new().build())
+        .plugin({
+            // Honor a proxy set via HTTP_PROXY/HTTPS_PROXY/ALL_PROXY for update
+            // downloads too, same as the backend health probe
+            let mut builder = tauri_plugin_updater::Builder::new();
+            if let Some(url) = http_client::updater_proxy_url() {
+                builder = builder.proxy(url);
+            }
+            builder.build()
+        })
         .setup(move |app| {
             println!("CNL Application starting...");
 
             // Start the FastAPI sidecar
-            if let Err(e) = sidecar_clone.start() {
+            let app_handle = app.handle().clone();
+            sidecar_clone.emit_lifecycle(&app_handle, "starting", None);
+            if let Err(e) = sidecar_clone.start(&app_handle) {
                 eprintln!("Failed to start backend server: {}", e);
+                sidecar_clone.emit_lifecycle(&app_handle, "unreachable", Some(e.clone()));
                 return Err(e.into());
             }
 
             // Wait for the backend to be ready
             let sidecar_for_health = Arc::clone(&sidecar_clone);
+            let app_for_health = app_handle.clone();
             tauri::async_runtime::spawn(async move {
-                if let Err(e) = sidecar_for_health.wait_for_health().await {
-                    eprintln!("Backend health check failed: {}", e);
+                match sidecar_for_health.wait_for_health(&app_for_health).await {
+                    Ok(()) => sidecar_for_health.emit_lifecycle(&app_for_health, "healthy", None),
+                    Err(e) => {
+                        eprintln!("Backend health check failed: {}", e);
+                        sidecar_for_health.emit_lifecycle(&app_for_health, "unreachable", Some(e));
+                    }
                 }
             });
 
+            // Supervise the sidecar for the lifetime of the app, restarting it on crash
+            let sidecar_for_supervisor = Arc::clone(&sidecar_clone);
+            let app_for_supervisor = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                sidecar_for_supervisor.supervise(app_for_supervisor).await;
+            });
+
             // Create system tray
             if let Err(e) = tray::create_system_tray(&app.handle()) {
                 eprintln!("Failed to create system tray: {}", e);
@@ -119,6 +132,7 @@ async fn main() {
 
             // Handle window close event - minimize to tray instead of quitting
             let sidecar_for_close = Arc::clone(&sidecar_clone);
+            let app_for_close = app.handle().clone();
             let window = app.get_webview_window("main").unwrap();
 
             window.on_window_event(move |event| {
@@ -136,18 +150,26 @@ async fn main() {
                         api.prevent_close();
                     }
                     tauri::WindowEvent::Destroyed => {
-                        // Only stop sidecar when window is actually destroyed (app quit)
+                        // Only stop sidecar when window is actually destroyed (app quit).
+                        // Stop off the event-loop thread (`stop` blocks synchronously for
+                        // up to GRACEFUL_SHUTDOWN_TIMEOUT) so a slow-to-exit backend can't
+                        // freeze the app while this handler runs.
                         println!("Window destroyed, stopping backend...");
-                        sidecar_for_close.stop();
+                        let sidecar = Arc::clone(&sidecar_for_close);
+                        let app = app_for_close.clone();
+                        tauri::async_runtime::spawn(async move {
+                            sidecar.stop_async().await;
+                            sidecar.emit_lifecycle(&app, "stopped", None);
+                        });
                     }
                     _ => {}
                 }
             });
 
-            // Register global keyboard shortcut
+            // Register persisted global shortcuts (show_window, quick_discovery, open_settings, ...)
             let app_handle = app.handle().clone();
-            if let Err(e) = setup_global_shortcut(&app_handle) {
-                eprintln!("Failed to register global shortcut: {}", e);
+            if let Err(e) = shortcuts::register_all(&app_handle) {
+                eprintln!("Failed to register global shortcuts: {}", e);
                 // Non-critical error, continue startup
             }
 
@@ -168,12 +190,17 @@ async fn main() {
         .invoke_handler(tauri::generate_handler![
             check_backend_health,
             is_backend_running,
+            get_backend_url,
+            get_sidecar_logs,
+            restart_backend,
             commands::is_autostart_enabled,
             commands::enable_autostart,
             commands::disable_autostart,
             commands::show_notification,
             commands::check_notification_permission,
-            commands::update_global_shortcut,
+            commands::request_notification_permission,
+            shortcuts::set_shortcut,
+            shortcuts::get_shortcuts,
             commands::get_version,
             commands::check_for_updates,
             commands::install_update
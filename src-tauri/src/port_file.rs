@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+/// Name of the file (in the OS config directory) the GUI writes its current
+/// backend port to, so the `meraki` companion CLI can find the running
+/// backend without a Tauri `AppHandle` of its own
+const PORT_FILE_NAME: &str = "backend-port.json";
+
+/// Directory the port file lives in, created on first write if missing
+fn dir() -> Result<PathBuf, String> {
+    dirs::config_dir()
+        .map(|dir| dir.join("meraki-workflow"))
+        .ok_or_else(|| "Could not determine the OS config directory".to_string())
+}
+
+/// Persist the backend's currently bound port
+pub fn write(port: u16) -> Result<(), String> {
+    let dir = dir()?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create config dir: {}", e))?;
+
+    let contents = serde_json::json!({ "port": port }).to_string();
+    std::fs::write(dir.join(PORT_FILE_NAME), contents)
+        .map_err(|e| format!("Failed to write port file: {}", e))
+}
+
+/// Remove the port file once the backend has stopped
+pub fn clear() {
+    if let Ok(dir) = dir() {
+        let _ = std::fs::remove_file(dir.join(PORT_FILE_NAME));
+    }
+}
+
+/// Read back the backend's last-known port
+pub fn read() -> Result<u16, String> {
+    let path = dir()?.join(PORT_FILE_NAME);
+    let contents = std::fs::read_to_string(path)
+        .map_err(|_| "Backend is not running (no port file found)".to_string())?;
+
+    let value: serde_json::Value =
+        serde_json::from_str(&contents).map_err(|e| format!("Failed to parse port file: {}", e))?;
+
+    value
+        .get("port")
+        .and_then(|v| v.as_u64())
+        .map(|port| port as u16)
+        .ok_or_else(|| "Port file is missing its 'port' field".to_string())
+}
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+
+use crate::{tray, AppState};
+
+/// Name of the persisted shortcuts file within the app's config directory
+const CONFIG_FILE_NAME: &str = "shortcuts.json";
+
+/// Default action -> accelerator bindings registered the first time the app runs
+fn default_bindings() -> HashMap<String, String> {
+    let show_window = if cfg!(target_os = "macos") {
+        "Cmd+Shift+M"
+    } else {
+        "Ctrl+Shift+M"
+    };
+
+    HashMap::from([
+        ("show_window".to_string(), show_window.to_string()),
+        ("quick_discovery".to_string(), "Ctrl+Shift+D".to_string()),
+        ("open_settings".to_string(), "Ctrl+Shift+S".to_string()),
+        ("restart_backend".to_string(), "Ctrl+Shift+R".to_string()),
+        ("health_check".to_string(), "Ctrl+Shift+H".to_string()),
+    ])
+}
+
+fn config_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to resolve app config dir: {}", e))?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create app config dir: {}", e))?;
+    Ok(dir.join(CONFIG_FILE_NAME))
+}
+
+/// Load persisted shortcut bindings, falling back to the defaults if none are saved yet
+fn load_bindings(app: &AppHandle) -> HashMap<String, String> {
+    let path = match config_path(app) {
+        Ok(path) => path,
+        Err(e) => {
+            eprintln!("{}", e);
+            return default_bindings();
+        }
+    };
+
+    match fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+            eprintln!("Failed to parse shortcuts config, using defaults: {}", e);
+            default_bindings()
+        }),
+        Err(_) => default_bindings(),
+    }
+}
+
+fn save_bindings(app: &AppHandle, bindings: &HashMap<String, String>) -> Result<(), String> {
+    let path = config_path(app)?;
+    let contents = serde_json::to_string_pretty(bindings)
+        .map_err(|e| format!("Failed to serialize shortcuts: {}", e))?;
+    fs::write(&path, contents).map_err(|e| format!("Failed to write shortcuts config: {}", e))
+}
+
+/// Register every persisted (or default) shortcut binding. Called on startup.
+pub fn register_all(app: &AppHandle) -> Result<(), String> {
+    apply_bindings(app, &load_bindings(app))
+}
+
+/// Unregister whatever is currently bound and register `bindings` in its place.
+/// Per-binding parse/registration failures are logged and surfaced to the frontend
+/// via a `shortcut-registration-failed` event rather than aborting startup.
+fn apply_bindings(app: &AppHandle, bindings: &HashMap<String, String>) -> Result<(), String> {
+    app.global_shortcut()
+        .unregister_all()
+        .map_err(|e| format!("Failed to unregister existing shortcuts: {:?}", e))?;
+
+    for (action, combo) in bindings {
+        if let Err(e) = register_one(app, action.clone(), combo) {
+            eprintln!("Failed to register shortcut {} -> {}: {}", action, combo, e);
+            if let Err(emit_err) = app.emit(
+                "shortcut-registration-failed",
+                serde_json::json!({ "action": action, "combo": combo, "error": e }),
+            ) {
+                eprintln!(
+                    "Failed to emit shortcut-registration-failed event: {}",
+                    emit_err
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn register_one(app: &AppHandle, action: String, combo: &str) -> Result<(), String> {
+    let shortcut: Shortcut = combo
+        .parse()
+        .map_err(|e| format!("Invalid shortcut '{}': {:?}", combo, e))?;
+
+    let app_handle = app.clone();
+    app.global_shortcut()
+        .on_shortcut(shortcut, move |_app, _shortcut, _event| {
+            dispatch_action(&app_handle, &action);
+        })
+        .map_err(|e| format!("Failed to register shortcut '{}': {:?}", combo, e))
+}
+
+/// Route a fired shortcut to its named action handler
+fn dispatch_action(app: &AppHandle, action: &str) {
+    match action {
+        "show_window" => tray::toggle_main_window(app),
+        "quick_discovery" => {
+            let app_handle = app.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = tray::run_quick_discovery(&app_handle).await {
+                    eprintln!("Quick discovery (shortcut) failed: {}", e);
+                }
+            });
+        }
+        "open_settings" => {
+            if let Err(e) = tray::show_settings_window(app) {
+                eprintln!("Failed to show settings window: {}", e);
+            }
+        }
+        "restart_backend" => {
+            let app_handle = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let sidecar = app_handle.state::<AppState>().sidecar.clone();
+                if let Err(e) = sidecar.restart(&app_handle).await {
+                    eprintln!("Manual restart (shortcut) failed: {}", e);
+                }
+            });
+        }
+        "health_check" => {
+            let app_handle = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let healthy = app_handle.state::<AppState>().sidecar.is_running();
+                if let Err(e) = app_handle.emit(
+                    "backend-health-check",
+                    serde_json::json!({ "healthy": healthy }),
+                ) {
+                    eprintln!("Failed to emit backend-health-check event: {}", e);
+                }
+            });
+        }
+        other => eprintln!("Unknown shortcut action: {}", other),
+    }
+}
+
+/// Tauri command: bind `combo` to `action`, rejecting it if another action already
+/// claims the same combo, then persist and re-register every binding
+#[tauri::command]
+pub fn set_shortcut(app: AppHandle, action: String, combo: String) -> Result<(), String> {
+    let _: Shortcut = combo
+        .parse()
+        .map_err(|e| format!("Invalid shortcut '{}': {:?}", combo, e))?;
+
+    let mut bindings = load_bindings(&app);
+
+    if let Some(conflicting_action) =
+        bindings
+            .iter()
+            .find_map(|(existing_action, existing_combo)| {
+                (*existing_combo == combo && *existing_action != action)
+                    .then(|| existing_action.clone())
+            })
+    {
+        return Err(format!(
+            "'{}' is already bound to action '{}'",
+            combo, conflicting_action
+        ));
+    }
+
+    bindings.insert(action, combo);
+    save_bindings(&app, &bindings)?;
+    apply_bindings(&app, &bindings)
+}
+
+/// Tauri command: return the currently persisted (or default) shortcut bindings
+#[tauri::command]
+pub fn get_shortcuts(app: AppHandle) -> HashMap<String, String> {
+    load_bindings(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_bindings_cover_known_actions() {
+        let bindings = default_bindings();
+        assert!(bindings.contains_key("show_window"));
+        assert!(bindings.contains_key("quick_discovery"));
+        assert!(bindings.contains_key("open_settings"));
+    }
+
+    #[test]
+    fn test_default_bindings_parse_as_shortcuts() {
+        for combo in default_bindings().values() {
+            let result: Result<Shortcut, _> = combo.parse();
+            assert!(result.is_ok(), "Failed to parse default binding: {}", combo);
+        }
+    }
+}
@@ -1,10 +1,47 @@
-use std::process::{Child, Command};
-use std::time::{Duration, Instant};
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read};
+use std::net::TcpListener;
+use std::process::{Child, Command, Stdio};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::port_file;
+
+/// Default maximum number of consecutive restart attempts before the
+/// supervisor gives up, used when no override is configured
+const DEFAULT_MAX_RESTART_ATTEMPTS: u32 = 5;
+
+/// Name of the persisted supervisor config file within the app's config directory
+const SUPERVISOR_CONFIG_FILE_NAME: &str = "supervisor.json";
+
+/// Maximum number of log lines retained in the in-memory ring buffer
+const LOG_BUFFER_CAPACITY: usize = 500;
+
+/// How long to wait for the process to exit after SIGTERM before escalating to SIGKILL
+const GRACEFUL_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often the supervisor polls `is_running()`/the health endpoint
+const SUPERVISE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long `wait_for_health` allows the backend to come up, and the grace
+/// period the supervisor gives a freshly spawned sidecar before its first
+/// check. Shared so the two can't drift apart and race each other.
+const STARTUP_HEALTH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Initial backoff delay between restart attempts, doubled on each failure
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Backoff delay is capped at this value
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
 
 /// Manages the FastAPI backend server as a sidecar process
 pub struct SidecarManager {
     process: Arc<Mutex<Option<Child>>>,
+    restart_count: Arc<Mutex<u32>>,
+    last_error: Arc<Mutex<Option<String>>>,
+    port: Arc<Mutex<Option<u16>>>,
+    logs: Arc<Mutex<VecDeque<String>>>,
 }
 
 impl SidecarManager {
@@ -12,9 +49,53 @@ impl SidecarManager {
     pub fn new() -> Self {
         Self {
             process: Arc::new(Mutex::new(None)),
+            restart_count: Arc::new(Mutex::new(0)),
+            last_error: Arc::new(Mutex::new(None)),
+            port: Arc::new(Mutex::new(None)),
+            logs: Arc::new(Mutex::new(VecDeque::with_capacity(LOG_BUFFER_CAPACITY))),
         }
     }
 
+    /// Last `LOG_BUFFER_CAPACITY` lines captured from the sidecar's stdout/stderr
+    pub fn logs(&self) -> Vec<String> {
+        self.logs.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Number of consecutive restarts the supervisor has performed since the last healthy check
+    pub fn restart_count(&self) -> u32 {
+        *self.restart_count.lock().unwrap()
+    }
+
+    /// Last error observed by the supervisor, if any
+    pub fn last_error(&self) -> Option<String> {
+        self.last_error.lock().unwrap().clone()
+    }
+
+    /// Port the sidecar is currently bound to, if it has been started
+    pub fn port(&self) -> Option<u16> {
+        *self.port.lock().unwrap()
+    }
+
+    /// Base URL of the running sidecar, e.g. `http://127.0.0.1:54321`
+    pub fn base_url(&self) -> Result<String, String> {
+        self.port()
+            .map(|port| format!("http://127.0.0.1:{}", port))
+            .ok_or_else(|| "Sidecar has not been started".to_string())
+    }
+
+    /// Bind an ephemeral free port on 127.0.0.1, releasing the listener immediately
+    /// so uvicorn can bind it in turn
+    fn allocate_port(&self) -> Result<u16, String> {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .map_err(|e| format!("Failed to bind ephemeral port: {}", e))?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| format!("Failed to read bound port: {}", e))?
+            .port();
+        drop(listener);
+        Ok(port)
+    }
+
     /// Find Python executable in the system
     /// Tries: python3 -> python
     fn find_python(&self) -> Result<String, String> {
@@ -28,13 +109,18 @@ impl SidecarManager {
         Err("Python not found. Please install Python 3.10 or higher.".to_string())
     }
 
-    /// Start the FastAPI server as a child process
-    pub fn start(&self) -> Result<(), String> {
+    /// Start the FastAPI server as a child process on a freshly allocated ephemeral port.
+    /// Stdout/stderr are piped and streamed to the frontend as `sidecar-log` events.
+    pub fn start(&self, app: &AppHandle) -> Result<(), String> {
         let python = self.find_python()?;
+        let port = self.allocate_port()?;
 
-        println!("Starting FastAPI server with {}...", python);
+        println!(
+            "Starting FastAPI server with {} on port {}...",
+            python, port
+        );
 
-        let child = Command::new(python)
+        let mut child = Command::new(python)
             .args([
                 "-m",
                 "uvicorn",
@@ -42,28 +128,94 @@ impl SidecarManager {
                 "--host",
                 "127.0.0.1",
                 "--port",
-                "3141",
+                &port.to_string(),
             ])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
             .spawn()
             .map_err(|e| format!("Failed to start FastAPI server: {}", e))?;
 
+        if let Some(stdout) = child.stdout.take() {
+            self.spawn_log_reader(stdout, "stdout", app.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            self.spawn_log_reader(stderr, "stderr", app.clone());
+        }
+
         *self.process.lock().unwrap() = Some(child);
+        *self.port.lock().unwrap() = Some(port);
+
+        if let Err(e) = port_file::write(port) {
+            eprintln!(
+                "Failed to persist backend port for the CLI companion: {}",
+                e
+            );
+        }
 
         println!("FastAPI server process started, waiting for health check...");
 
         Ok(())
     }
 
+    /// Read lines from a piped stdout/stderr stream, keep them in the ring buffer,
+    /// and forward each as a `sidecar-log` event
+    fn spawn_log_reader<T: Read + Send + 'static>(
+        &self,
+        stream: T,
+        label: &'static str,
+        app: AppHandle,
+    ) {
+        let logs = Arc::clone(&self.logs);
+
+        // This loop blocks synchronously for the lifetime of the sidecar
+        // process (`BufReader::lines()` over a plain `std::process` pipe
+        // never yields), so it runs on a blocking-pool thread rather than
+        // `spawn` - two of these run per `start()`, and pinning two tokio
+        // worker threads for the process's whole lifetime can starve the
+        // runtime on a low-core machine.
+        tauri::async_runtime::spawn_blocking(move || {
+            let reader = BufReader::new(stream);
+
+            for line in reader.lines() {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(_) => break,
+                };
+
+                {
+                    let mut buffer = logs.lock().unwrap();
+                    if buffer.len() >= LOG_BUFFER_CAPACITY {
+                        buffer.pop_front();
+                    }
+                    buffer.push_back(format!("[{}] {}", label, line));
+                }
+
+                let level = if label == "stderr" { "error" } else { "info" };
+                if let Err(e) = app.emit(
+                    "sidecar-log",
+                    serde_json::json!({
+                        "stream": label,
+                        "level": level,
+                        "line": line,
+                    }),
+                ) {
+                    eprintln!("Failed to emit sidecar-log event: {}", e);
+                }
+            }
+        });
+    }
+
     /// Wait for the server to become healthy by polling the health endpoint
-    /// Timeout: 10 seconds
-    pub async fn wait_for_health(&self) -> Result<(), String> {
+    /// Timeout: `STARTUP_HEALTH_TIMEOUT`
+    pub async fn wait_for_health(&self, app: &AppHandle) -> Result<(), String> {
+        let base_url = self.base_url()?;
         let start = Instant::now();
-        let timeout = Duration::from_secs(10);
-        let client = reqwest::Client::new();
+        let timeout = STARTUP_HEALTH_TIMEOUT;
+        let client = crate::http_client::build(app);
 
         while start.elapsed() < timeout {
             match client
-                .get("http://127.0.0.1:3141/api/v1/health")
+                .get(format!("{}/api/v1/health", base_url))
                 .timeout(Duration::from_secs(2))
                 .send()
                 .await
@@ -81,17 +233,218 @@ impl SidecarManager {
         Err("Timeout waiting for FastAPI server to start".to_string())
     }
 
-    /// Stop the sidecar process gracefully
-    /// Tries SIGTERM first, then SIGKILL if needed
+    /// Stop and restart the sidecar on demand, reflecting each step through
+    /// the `backend://` lifecycle events. Shared by the `restart_backend`
+    /// command and its equivalent global shortcut action.
+    pub async fn restart(&self, app: &AppHandle) -> Result<(), String> {
+        println!("Restarting backend...");
+        self.stop_async().await;
+        self.emit_lifecycle(app, "starting", None);
+
+        if let Err(e) = self.start(app) {
+            self.emit_lifecycle(app, "unreachable", Some(e.clone()));
+            return Err(e);
+        }
+
+        match self.wait_for_health(app).await {
+            Ok(()) => {
+                self.emit_lifecycle(app, "healthy", None);
+                Ok(())
+            }
+            Err(e) => {
+                self.emit_lifecycle(app, "unreachable", Some(e.clone()));
+                Err(e)
+            }
+        }
+    }
+
+    /// Single health probe, used by the supervisor between full startup waits
+    async fn probe_health(&self, app: &AppHandle) -> bool {
+        let base_url = match self.base_url() {
+            Ok(url) => url,
+            Err(_) => return false,
+        };
+        let client = crate::http_client::build(app);
+
+        matches!(
+            client
+                .get(format!("{}/api/v1/health", base_url))
+                .timeout(Duration::from_secs(2))
+                .send()
+                .await,
+            Ok(response) if response.status().is_success()
+        )
+    }
+
+    /// Supervise the sidecar, restarting it with capped exponential backoff whenever
+    /// the process exits or fails its health check. Emits `sidecar-status` events
+    /// (`starting` / `healthy` / `restarting` / `failed`) so the frontend can show a
+    /// reconnecting banner. Gives up after `max_restart_attempts` (from the persisted
+    /// supervisor config, or `DEFAULT_MAX_RESTART_ATTEMPTS`) consecutive failures so a
+    /// permanently broken Python env surfaces a clear error instead of looping forever.
+    pub async fn supervise(self: Arc<Self>, app: AppHandle) {
+        let max_restart_attempts = max_restart_attempts(&app);
+        let mut backoff = INITIAL_BACKOFF;
+        let mut first_check = true;
+        let mut reported_healthy = false;
+        self.emit_status(&app, "starting", 0, None);
+
+        loop {
+            // Give a freshly (re)started sidecar the same grace period
+            // `wait_for_health` itself allows before the supervisor's own
+            // first check, so a backend that's merely slow to boot isn't
+            // mistaken for one that's unhealthy and restarted mid-startup.
+            tokio::time::sleep(if first_check {
+                STARTUP_HEALTH_TIMEOUT
+            } else {
+                SUPERVISE_POLL_INTERVAL
+            })
+            .await;
+            first_check = false;
+
+            if self.is_running() && self.probe_health(&app).await {
+                *self.restart_count.lock().unwrap() = 0;
+                *self.last_error.lock().unwrap() = None;
+                backoff = INITIAL_BACKOFF;
+                if !reported_healthy {
+                    self.emit_status(&app, "healthy", 0, None);
+                    reported_healthy = true;
+                }
+                continue;
+            }
+            reported_healthy = false;
+
+            let attempts = {
+                let mut count = self.restart_count.lock().unwrap();
+                *count += 1;
+                *count
+            };
+
+            if attempts > max_restart_attempts {
+                let message = format!(
+                    "Sidecar failed to recover after {} attempts, giving up",
+                    max_restart_attempts
+                );
+                eprintln!("{}", message);
+                *self.last_error.lock().unwrap() = Some(message.clone());
+                self.emit_status(&app, "failed", attempts, Some(message));
+                return;
+            }
+
+            eprintln!(
+                "Sidecar unhealthy, restarting (attempt {}/{})...",
+                attempts, max_restart_attempts
+            );
+            self.emit_status(&app, "restarting", attempts, None);
+            self.stop_async().await;
+
+            let restart_result = match self.start(&app) {
+                Ok(()) => self.wait_for_health(&app).await,
+                Err(e) => Err(e),
+            };
+
+            match restart_result {
+                Ok(()) => {
+                    *self.restart_count.lock().unwrap() = 0;
+                    *self.last_error.lock().unwrap() = None;
+                    backoff = INITIAL_BACKOFF;
+                    self.emit_status(&app, "healthy", 0, None);
+                    reported_healthy = true;
+                }
+                Err(e) => {
+                    eprintln!("Restart attempt {} failed: {}", attempts, e);
+                    *self.last_error.lock().unwrap() = Some(e.clone());
+                    self.emit_status(&app, "restarting", attempts, Some(e));
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+            }
+        }
+    }
+
+    /// Emit a `sidecar-status` event to the frontend
+    fn emit_status(
+        &self,
+        app: &AppHandle,
+        status: &str,
+        restart_count: u32,
+        last_error: Option<String>,
+    ) {
+        if let Err(e) = app.emit(
+            "sidecar-status",
+            serde_json::json!({
+                "status": status,
+                "restartCount": restart_count,
+                "lastError": last_error.clone(),
+            }),
+        ) {
+            eprintln!("Failed to emit sidecar-status event: {}", e);
+        }
+
+        let lifecycle_event = match status {
+            "healthy" => "healthy",
+            "starting" => "starting",
+            _ => "unreachable",
+        };
+        self.emit_lifecycle(app, lifecycle_event, last_error);
+    }
+
+    /// Emit a namespaced `backend://<event>` lifecycle event (`starting` /
+    /// `healthy` / `unreachable` / `stopped`) for the frontend's connection
+    /// banner, carrying the last error (if any) and a Unix timestamp so it
+    /// can auto-retry instead of polling `is_backend_running` on a timer
+    pub(crate) fn emit_lifecycle(&self, app: &AppHandle, event: &str, error: Option<String>) {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0);
+
+        if let Err(e) = app.emit(
+            &format!("backend://{}", event),
+            serde_json::json!({ "error": error, "timestamp": timestamp }),
+        ) {
+            eprintln!("Failed to emit backend://{} event: {}", event, e);
+        }
+    }
+
+    /// Stop the sidecar process gracefully.
+    /// Sends SIGTERM and waits up to `GRACEFUL_SHUTDOWN_TIMEOUT` for the process to exit
+    /// on its own (letting FastAPI run its shutdown hooks), only escalating to SIGKILL
+    /// if it's still alive once the grace period passes. On non-Unix platforms no
+    /// terminate signal is sent and the process is killed right away.
+    ///
+    /// Blocks the calling thread for up to `GRACEFUL_SHUTDOWN_TIMEOUT` - callers on an
+    /// async runtime thread (the supervisor, `restart`, anything driving a Tauri command)
+    /// should use `stop_async` instead so this can't stall other work sharing the pool.
     pub fn stop(&self) {
-        let mut process = self.process.lock().unwrap();
+        Self::stop_blocking(Arc::clone(&self.process), Arc::clone(&self.port));
+    }
+
+    /// Same as `stop`, but runs on a blocking-pool thread instead of the caller's, so
+    /// the up-to-`GRACEFUL_SHUTDOWN_TIMEOUT` wait can't pin down an async worker thread.
+    /// Used by the supervisor's auto-restart loop, `restart`, window close, and tray quit.
+    pub async fn stop_async(&self) {
+        let process = Arc::clone(&self.process);
+        let port = Arc::clone(&self.port);
+
+        if let Err(e) =
+            tauri::async_runtime::spawn_blocking(move || Self::stop_blocking(process, port)).await
+        {
+            eprintln!("Graceful shutdown task panicked: {}", e);
+        }
+    }
+
+    fn stop_blocking(process: Arc<Mutex<Option<Child>>>, port: Arc<Mutex<Option<u16>>>) {
+        let mut process = process.lock().unwrap();
 
         if let Some(ref mut child) = *process {
             println!("Stopping FastAPI server...");
 
-            // Try graceful shutdown first
-            if let Err(e) = child.kill() {
-                eprintln!("Error stopping server: {}", e);
+            if let Err(e) = Self::terminate_gracefully(child) {
+                eprintln!("Graceful shutdown failed ({}), forcing termination", e);
+                if let Err(e) = child.kill() {
+                    eprintln!("Error stopping server: {}", e);
+                }
             }
 
             // Wait for process to exit
@@ -103,6 +456,43 @@ impl SidecarManager {
         }
 
         *process = None;
+        *port.lock().unwrap() = None;
+        port_file::clear();
+    }
+
+    /// Send SIGTERM (Unix) and poll `try_wait` until the process exits or the grace
+    /// period expires
+    fn terminate_gracefully(child: &mut Child) -> Result<(), String> {
+        Self::send_terminate_signal(child)?;
+
+        let deadline = Instant::now() + GRACEFUL_SHUTDOWN_TIMEOUT;
+        while Instant::now() < deadline {
+            match child.try_wait() {
+                Ok(Some(_)) => return Ok(()),
+                Ok(None) => std::thread::sleep(Duration::from_millis(200)),
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+
+        Err("Timed out waiting for the process to exit after SIGTERM".to_string())
+    }
+
+    #[cfg(unix)]
+    fn send_terminate_signal(child: &Child) -> Result<(), String> {
+        let pid = child.id() as libc::pid_t;
+
+        // SAFETY: pid comes from a live Child we hold a reference to
+        if unsafe { libc::kill(pid, libc::SIGTERM) } == 0 {
+            Ok(())
+        } else {
+            Err(std::io::Error::last_os_error().to_string())
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn send_terminate_signal(_child: &Child) -> Result<(), String> {
+        // No portable SIGTERM equivalent outside Unix; callers fall back to kill()
+        Err("Graceful terminate is only supported on Unix".to_string())
     }
 
     /// Check if the sidecar process is still running
@@ -131,6 +521,27 @@ impl SidecarManager {
     }
 }
 
+/// Max consecutive restart attempts before the supervisor gives up, read from
+/// the persisted supervisor config (`maxRestartAttempts`) if present, falling
+/// back to `DEFAULT_MAX_RESTART_ATTEMPTS`
+fn max_restart_attempts(app: &AppHandle) -> u32 {
+    let dir = match app.path().app_config_dir() {
+        Ok(dir) => dir,
+        Err(_) => return DEFAULT_MAX_RESTART_ATTEMPTS,
+    };
+
+    let contents = match std::fs::read_to_string(dir.join(SUPERVISOR_CONFIG_FILE_NAME)) {
+        Ok(contents) => contents,
+        Err(_) => return DEFAULT_MAX_RESTART_ATTEMPTS,
+    };
+
+    serde_json::from_str::<serde_json::Value>(&contents)
+        .ok()
+        .and_then(|v| v.get("maxRestartAttempts")?.as_u64())
+        .map(|v| v as u32)
+        .unwrap_or(DEFAULT_MAX_RESTART_ATTEMPTS)
+}
+
 impl Drop for SidecarManager {
     fn drop(&mut self) {
         self.stop();
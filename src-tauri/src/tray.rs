@@ -1,19 +1,22 @@
+use std::sync::Arc;
 use tauri::{
     menu::{Menu, MenuItem, PredefinedMenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager, Runtime, AppHandle, Emitter,
+    AppHandle, Emitter, Manager, Runtime,
 };
-use std::sync::Arc;
+use tauri_plugin_notification::{NotificationExt, PermissionState};
 
 /// Create the system tray icon and menu
 ///
 /// Menu items:
+/// - Backend: <status> (non-clickable, reflects the latest `sidecar-status` event)
 /// - Open CNL (show/focus main window)
-/// - Quick Discovery (POST to /api/v1/discovery/full)
-/// - Settings (show window + navigate to /settings)
+/// - Quick Discovery (POST to /api/v1/discovery/full) - disabled while the backend is down
+/// - Settings (show window + navigate to /settings) - disabled while the backend is down
 /// - Quit (stop sidecar + exit app)
 pub fn create_system_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
     // Create menu items
+    let status = MenuItem::with_id(app, "status", "Backend: starting", false, None::<&str>)?;
     let open = MenuItem::with_id(app, "open", "Open CNL", true, None::<&str>)?;
     let discovery = MenuItem::with_id(app, "discovery", "Quick Discovery", true, None::<&str>)?;
     let settings = MenuItem::with_id(app, "settings", "Settings", true, None::<&str>)?;
@@ -22,14 +25,16 @@ pub fn create_system_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
     // Create separator
     let separator = PredefinedMenuItem::separator(app)?;
 
-    // Build menu: Open CNL, ---, Quick Discovery, Settings, ---, Quit
+    // Build menu: Backend status, ---, Open CNL, ---, Quick Discovery, Settings, ---, Quit
     let menu = Menu::with_items(
         app,
-        &[&open, &separator, &discovery, &settings, &separator, &quit],
+        &[
+            &status, &separator, &open, &separator, &discovery, &settings, &separator, &quit,
+        ],
     )?;
 
     // Build tray icon with menu
-    let _tray = TrayIconBuilder::new()
+    let tray = TrayIconBuilder::new()
         .icon(app.default_window_icon().ok_or("No default icon")?.clone())
         .menu(&menu)
         .tooltip("CNL - Cisco Neural Language")
@@ -37,10 +42,97 @@ pub fn create_system_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
         .on_tray_icon_event(on_tray_icon_event)
         .build(app)?;
 
+    watch_sidecar_status(app, tray, status, discovery, settings);
+
     println!("System tray created successfully");
     Ok(())
 }
 
+/// Subscribe to `sidecar-status` events and reflect backend health in the tray:
+/// swap the icon, update the tooltip and status line, and enable/disable the
+/// `discovery`/`settings` items while the backend is down
+fn watch_sidecar_status<R: Runtime>(
+    app: &AppHandle<R>,
+    tray: tauri::tray::TrayIcon<R>,
+    status_item: MenuItem<R>,
+    discovery: MenuItem<R>,
+    settings: MenuItem<R>,
+) {
+    let app_handle = app.clone();
+
+    app.listen("sidecar-status", move |event| {
+        let payload: serde_json::Value = match serde_json::from_str(event.payload()) {
+            Ok(value) => value,
+            Err(e) => {
+                eprintln!("Failed to parse sidecar-status payload: {}", e);
+                return;
+            }
+        };
+
+        let status = payload
+            .get("status")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown");
+
+        let (label, tooltip, icon_variant, backend_up) = match status {
+            "healthy" => ("Backend: healthy", "CNL - Backend healthy", "healthy", true),
+            "starting" => (
+                "Backend: starting",
+                "CNL - Backend starting",
+                "degraded",
+                false,
+            ),
+            "restarting" => (
+                "Backend: reconnecting",
+                "CNL - Backend reconnecting",
+                "degraded",
+                false,
+            ),
+            _ => (
+                "Backend: offline",
+                "CNL - Backend offline",
+                "offline",
+                false,
+            ),
+        };
+
+        if let Err(e) = status_item.set_text(label) {
+            eprintln!("Failed to update tray status item: {}", e);
+        }
+        if let Err(e) = tray.set_tooltip(Some(tooltip)) {
+            eprintln!("Failed to update tray tooltip: {}", e);
+        }
+        if let Err(e) = discovery.set_enabled(backend_up) {
+            eprintln!("Failed to toggle discovery menu item: {}", e);
+        }
+        if let Err(e) = settings.set_enabled(backend_up) {
+            eprintln!("Failed to toggle settings menu item: {}", e);
+        }
+        if let Some(icon) = load_tray_icon(&app_handle, icon_variant) {
+            if let Err(e) = tray.set_icon(Some(icon)) {
+                eprintln!("Failed to update tray icon: {}", e);
+            }
+        }
+    });
+}
+
+/// Load a bundled tray icon variant (`healthy` / `degraded` / `offline`); returns
+/// `None` (keeping whatever icon is already set) if the resource isn't bundled
+fn load_tray_icon<R: Runtime>(
+    app: &AppHandle<R>,
+    variant: &str,
+) -> Option<tauri::image::Image<'static>> {
+    let path = app
+        .path()
+        .resolve(
+            format!("icons/tray-{}.png", variant),
+            tauri::path::BaseDirectory::Resource,
+        )
+        .ok()?;
+
+    tauri::image::Image::from_path(path).ok()
+}
+
 /// Handle tray menu item clicks
 fn on_menu_event<R: Runtime>(app: &AppHandle<R>, event: tauri::menu::MenuEvent) {
     match event.id.as_ref() {
@@ -54,11 +146,9 @@ fn on_menu_event<R: Runtime>(app: &AppHandle<R>, event: tauri::menu::MenuEvent)
             tauri::async_runtime::spawn(async move {
                 if let Err(e) = run_quick_discovery(&app_handle).await {
                     eprintln!("Quick discovery failed: {}", e);
-                    if let Err(notify_err) = send_notification(
-                        &app_handle,
-                        "Discovery Failed",
-                        &format!("Error: {}", e),
-                    ) {
+                    if let Err(notify_err) =
+                        send_notification(&app_handle, "Discovery Failed", &format!("Error: {}", e))
+                    {
                         eprintln!("Failed to send notification: {}", notify_err);
                     }
                 }
@@ -107,8 +197,30 @@ fn show_main_window<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
     Ok(())
 }
 
+/// Show the main window if hidden, or just focus it if already visible. Used by
+/// the `show_window` global shortcut action.
+pub(crate) fn toggle_main_window<R: Runtime>(app: &AppHandle<R>) {
+    let Some(window) = app.get_webview_window("main") else {
+        eprintln!("Main window not found");
+        return;
+    };
+
+    if window.is_visible().unwrap_or(false) {
+        if let Err(e) = window.set_focus() {
+            eprintln!("Failed to focus window: {}", e);
+        }
+    } else {
+        if let Err(e) = window.show() {
+            eprintln!("Failed to show window: {}", e);
+        }
+        if let Err(e) = window.set_focus() {
+            eprintln!("Failed to focus window: {}", e);
+        }
+    }
+}
+
 /// Show settings window (main window + navigate to settings)
-fn show_settings_window<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
+pub(crate) fn show_settings_window<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
     if let Some(window) = app.get_webview_window("main") {
         window.show()?;
         window.set_focus()?;
@@ -127,6 +239,8 @@ fn show_settings_window<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
 
 /// Quit the application (stop sidecar + exit)
 fn quit_application<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
+    use crate::AppState;
+
     println!("Quit requested from tray, stopping application...");
 
     // Close all windows first
@@ -136,23 +250,36 @@ fn quit_application<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
         }
     }
 
-    // The window close handler will trigger sidecar shutdown
-    // Exit the application
-    app.exit(0);
+    // Stop the sidecar off the UI thread and wait for it to actually finish
+    // before exiting. The `CloseRequested` handler hides the window instead
+    // of closing it, so `window.close()` above can't be relied on to trigger
+    // the `Destroyed`-driven shutdown - quit has to drive it directly.
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let sidecar = app.state::<AppState>().sidecar.clone();
+        sidecar.stop_async().await;
+        sidecar.emit_lifecycle(&app, "stopped", None);
+        app.exit(0);
+    });
+
     Ok(())
 }
 
 /// Run quick discovery by posting to the backend API
-async fn run_quick_discovery<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+pub(crate) async fn run_quick_discovery<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    use crate::AppState;
+
     println!("Running quick discovery from tray...");
 
     // Send starting notification
     send_notification(app, "Quick Discovery", "Starting discovery...")?;
 
+    let base_url = app.state::<AppState>().sidecar.base_url()?;
+
     // POST to backend API
-    let client = reqwest::Client::new();
+    let client = crate::http_client::build(app);
     let response = client
-        .post("http://127.0.0.1:3141/api/v1/discovery/full")
+        .post(format!("{}/api/v1/discovery/full", base_url))
         .timeout(std::time::Duration::from_secs(60))
         .send()
         .await
@@ -177,7 +304,10 @@ async fn run_quick_discovery<R: Runtime>(app: &AppHandle<R>) -> Result<(), Strin
             .map(|arr| arr.len())
             .unwrap_or(0);
 
-        let message = format!("Discovery complete: {} networks, {} devices", networks, devices);
+        let message = format!(
+            "Discovery complete: {} networks, {} devices",
+            networks, devices
+        );
         send_notification(app, "Quick Discovery Complete", &message)?;
 
         println!("{}", message);
@@ -189,12 +319,23 @@ async fn run_quick_discovery<R: Runtime>(app: &AppHandle<R>) -> Result<(), Strin
     }
 }
 
-/// Send a native OS notification
+/// Send a native OS notification, prompting the frontend to request permission
+/// instead if it has been denied
 fn send_notification<R: Runtime>(
     app: &AppHandle<R>,
     title: &str,
     body: &str,
 ) -> Result<(), String> {
+    if app.notification().permission_state().ok() == Some(PermissionState::Denied) {
+        if let Err(e) = app.emit("notification-permission-required", ()) {
+            eprintln!(
+                "Failed to emit notification-permission-required event: {}",
+                e
+            );
+        }
+        return Err("Notification permission denied".to_string());
+    }
+
     // Emit notification event to frontend for handling via tauri-plugin-notification
     if let Err(e) = app.emit(
         "show-notification",
@@ -216,8 +357,9 @@ mod tests {
 
     #[test]
     fn test_menu_item_ids() {
-        let items = vec!["open", "discovery", "settings", "quit"];
-        assert_eq!(items.len(), 4);
+        let items = vec!["status", "open", "discovery", "settings", "quit"];
+        assert_eq!(items.len(), 5);
+        assert!(items.contains(&"status"));
         assert!(items.contains(&"open"));
         assert!(items.contains(&"discovery"));
         assert!(items.contains(&"settings"));
@@ -1,6 +1,28 @@
-use tauri::{AppHandle, Manager};
-use tauri_plugin_updater::UpdaterExt;
 use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+/// Name of the persisted updater config file within the app's config directory
+const CONFIG_FILE_NAME: &str = "updater.json";
+
+/// Whether update checks should show a native confirm dialog with release notes
+/// before downloading, in addition to emitting `update-available`. Reads a
+/// `dialog_mode` flag from the persisted updater config, defaulting to `false`.
+fn is_dialog_mode_enabled(app: &AppHandle) -> bool {
+    let Ok(dir) = app.path().app_config_dir() else {
+        return false;
+    };
+
+    let Ok(contents) = std::fs::read_to_string(dir.join(CONFIG_FILE_NAME)) else {
+        return false;
+    };
+
+    serde_json::from_str::<serde_json::Value>(&contents)
+        .ok()
+        .and_then(|value| value.get("dialog_mode").and_then(|v| v.as_bool()))
+        .unwrap_or(false)
+}
 
 /// Check for updates in the background on app startup
 pub async fn check_for_updates_on_startup(app: AppHandle) {
@@ -30,7 +52,9 @@ pub fn start_periodic_update_check(app: AppHandle) {
     });
 }
 
-/// Check for updates and emit event to frontend if available
+/// Check for updates and emit event to frontend if available. In dialog mode,
+/// also prompts the user with a native confirm dialog and only downloads/installs
+/// once they accept.
 async fn check_and_notify_update(app: &AppHandle) -> Result<(), String> {
     let updater = app.updater();
 
@@ -44,31 +68,111 @@ async fn check_and_notify_update(app: &AppHandle) -> Result<(), String> {
                     println!("Update available: version {}", version);
 
                     // Emit event to frontend
-                    if let Err(e) = app.emit("update-available", serde_json::json!({
-                        "version": version,
-                        "notes": body,
-                        "date": update.date.clone(),
-                    })) {
+                    if let Err(e) = app.emit(
+                        "update-available",
+                        serde_json::json!({
+                            "version": version,
+                            "notes": body,
+                            "date": update.date.clone(),
+                        }),
+                    ) {
                         eprintln!("Failed to emit update-available event: {}", e);
                     }
 
+                    if is_dialog_mode_enabled(app) {
+                        prompt_and_install(app.clone(), update, version, body);
+                    }
+
                     Ok(())
                 }
                 Ok(None) => {
                     println!("No update available");
                     Ok(())
                 }
-                Err(e) => {
-                    Err(format!("Failed to check for updates: {}", e))
-                }
+                Err(e) => Err(format!("Failed to check for updates: {}", e)),
             }
         }
-        Err(e) => {
-            Err(format!("Failed to get updater instance: {}", e))
-        }
+        Err(e) => Err(format!("Failed to get updater instance: {}", e)),
     }
 }
 
+/// Show a native confirm dialog with the release notes and, if the user accepts,
+/// download and install the update
+fn prompt_and_install(app: AppHandle, update: Update, version: String, notes: String) {
+    let app_for_install = app.clone();
+
+    app.dialog()
+        .message(notes)
+        .title(format!("Update {} available", version))
+        .kind(MessageDialogKind::Info)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "Install".to_string(),
+            "Later".to_string(),
+        ))
+        .show(move |accepted| {
+            if !accepted {
+                println!("Update declined by user");
+                return;
+            }
+
+            let app_handle = app_for_install.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = download_and_install(app_handle, update).await {
+                    eprintln!("Update install failed: {}", e);
+                }
+            });
+        });
+}
+
+/// Download and install an available update, emitting `update-download-progress`
+/// events as chunks arrive and `update-ready-to-restart` once it's ready to relaunch.
+/// Shared by the dialog-mode flow and the manual `install_update` command.
+pub async fn download_and_install(app: AppHandle, update: Update) -> Result<(), String> {
+    let version = update.version.clone();
+
+    if let Err(e) = app.emit(
+        "update-installing",
+        serde_json::json!({ "version": version }),
+    ) {
+        eprintln!("Failed to emit update-installing event: {}", e);
+    }
+
+    let app_for_progress = app.clone();
+    let mut downloaded: u64 = 0;
+
+    update
+        .download_and_install(move |chunk_length, content_length| {
+            downloaded += chunk_length as u64;
+            let percent = content_length
+                .map(|total| (downloaded as f64 / total as f64) * 100.0)
+                .unwrap_or(0.0);
+
+            if let Err(e) = app_for_progress.emit(
+                "update-download-progress",
+                serde_json::json!({
+                    "downloaded": downloaded,
+                    "total": content_length,
+                    "percent": percent,
+                }),
+            ) {
+                eprintln!("Failed to emit update-download-progress event: {}", e);
+            }
+        })
+        .await
+        .map_err(|e| format!("Failed to install update: {}", e))?;
+
+    println!("Update installed successfully");
+
+    if let Err(e) = app.emit(
+        "update-ready-to-restart",
+        serde_json::json!({ "version": version }),
+    ) {
+        eprintln!("Failed to emit update-ready-to-restart event: {}", e);
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;